@@ -2,13 +2,19 @@ pub mod planner;
 pub mod reader;
 pub mod synthesizer;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use std::sync::Arc;
 use std::time::Instant;
 
 use crate::config::Config;
-use crate::instrumentation::{HopLog, RunLog, RunLogger};
-use crate::llm::LlmClient;
-use crate::retrieval::QdrantRetriever;
+use crate::instrumentation::{
+    Component, HopLog, Metrics, RunLog, RunLogger, RunStore, SqliteRunLogger, DEFAULT_DB_PATH,
+};
+use crate::llm::{AnthropicClient, LlmClient, LlmProvider, LlmResponse, StreamChunk};
+use crate::retrieval::{
+    Embedder, FastEmbedLocal, OllamaEmbedder, OpenAiEmbedder, QdrantRetriever, SemanticCache,
+};
 
 use planner::Planner;
 use reader::{Reader, ReaderDecision};
@@ -19,34 +25,162 @@ pub struct Agent {
     reader: Reader,
     synthesizer: Synthesizer,
     retriever: QdrantRetriever,
+    embedder: Option<Arc<dyn Embedder>>,
+    cache: Option<SemanticCache>,
     config: Config,
-    logger: RunLogger,
+    logger: Box<dyn RunStore>,
 }
 
 impl Agent {
     pub async fn new(config: Config) -> Result<Self> {
-        let llm = LlmClient::new(&config.llm_api_key, &config.llm_base_url);
+        let planner_provider = build_provider(&config.planner_provider, &config)?;
+        let reader_provider = build_provider(&config.reader_provider, &config)?;
+        let synthesizer_provider = build_provider(&config.synthesizer_provider, &config)?;
+
         let retriever = QdrantRetriever::new(
             &config.qdrant_url,
             config.qdrant_api_key.as_deref(),
             &config.qdrant_collection,
             &config.embedding_model,
+            config.max_retries,
         )
         .await?;
-        let logger = RunLogger::new("logs")?;
+        let logger = build_run_store(&config)?;
+
+        // Cloud inference lets Qdrant embed queries server-side; otherwise we embed client-side
+        // through whichever provider EMBEDDING_PROVIDER selects.
+        let embedder: Option<Arc<dyn Embedder>> = if config.cloud_inference {
+            None
+        } else {
+            Some(build_embedder(&config)?)
+        };
+
+        let cache = match &config.qdrant_cache_collection {
+            Some(cache_collection) => Some(
+                SemanticCache::new(
+                    &config.qdrant_url,
+                    config.qdrant_api_key.as_deref(),
+                    cache_collection,
+                    &config.embedding_model,
+                    config.semantic_cache_threshold,
+                )
+                .await?,
+            ),
+            None => None,
+        };
 
         Ok(Self {
-            planner: Planner::new(llm.clone(), config.planner_model.clone()),
-            reader: Reader::new(llm.clone(), config.reader_model.clone()),
-            synthesizer: Synthesizer::new(llm, config.synthesizer_model.clone()),
+            planner: Planner::new(planner_provider, config.planner_model.clone()),
+            reader: Reader::new(reader_provider, config.reader_model.clone()),
+            synthesizer: Synthesizer::new(synthesizer_provider, config.synthesizer_model.clone()),
             retriever,
+            embedder,
+            cache,
             config,
             logger,
         })
     }
 
     pub async fn ask(&self, question: &str, verbose: bool) -> Result<RunLog> {
+        Metrics::global().run_started();
+        let run_start = Instant::now();
+        let (hops, accumulated_context, plan_latency, plan_response) =
+            self.plan_and_search(question, verbose).await?;
+
+        // Synthesize final answer
+        let synth_start = Instant::now();
+        let (answer, synth_response) = self
+            .synthesizer
+            .synthesize(question, &accumulated_context)
+            .await?;
+        let synth_latency = synth_start.elapsed().as_millis() as u64;
+        self.record_synthesis_metrics(synth_latency, &synth_response);
+
+        if verbose {
+            eprintln!("[synthesizer] Generated answer in {}ms", synth_latency);
+        }
+
+        let run_log = self.build_run_log(
+            question,
+            run_start,
+            hops,
+            plan_latency,
+            &plan_response,
+            synth_latency,
+            &synth_response,
+            answer,
+        );
+        Metrics::global().run_completed(run_log.hops.len());
+
+        self.logger.write(&run_log)?;
+
+        Ok(run_log)
+    }
+
+    /// Like [`ask`](Self::ask), but streams the synthesized answer to `on_chunk` as it's
+    /// generated instead of returning it all at once. The hop loop itself still runs to
+    /// completion before streaming starts, since the synthesizer needs the full accumulated
+    /// context up front.
+    pub async fn ask_streaming(
+        &self,
+        question: &str,
+        verbose: bool,
+        mut on_chunk: impl FnMut(&str),
+    ) -> Result<RunLog> {
+        Metrics::global().run_started();
         let run_start = Instant::now();
+        let (hops, accumulated_context, plan_latency, plan_response) =
+            self.plan_and_search(question, verbose).await?;
+
+        let synth_start = Instant::now();
+        let mut stream = Box::pin(
+            self.synthesizer
+                .synthesize_stream(question, &accumulated_context),
+        );
+        let mut answer = String::new();
+        let mut synth_response = None;
+        while let Some(chunk) = stream.next().await {
+            match chunk? {
+                StreamChunk::Delta(delta) => {
+                    on_chunk(&delta);
+                    answer.push_str(&delta);
+                }
+                StreamChunk::Done(response) => synth_response = Some(response),
+            }
+        }
+        let synth_response = synth_response.context("Synthesizer stream ended without a usage summary")?;
+        let synth_latency = synth_start.elapsed().as_millis() as u64;
+        self.record_synthesis_metrics(synth_latency, &synth_response);
+
+        if verbose {
+            eprintln!("[synthesizer] Generated answer in {}ms", synth_latency);
+        }
+
+        let run_log = self.build_run_log(
+            question,
+            run_start,
+            hops,
+            plan_latency,
+            &plan_response,
+            synth_latency,
+            &synth_response,
+            answer,
+        );
+        Metrics::global().run_completed(run_log.hops.len());
+
+        self.logger.write(&run_log)?;
+
+        Ok(run_log)
+    }
+
+    /// Runs the planner and the hop loop, returning the accumulated evidence the synthesizer
+    /// needs. Shared by [`ask`](Self::ask) and [`ask_streaming`](Self::ask_streaming), which
+    /// only differ in how they synthesize the final answer.
+    async fn plan_and_search(
+        &self,
+        question: &str,
+        verbose: bool,
+    ) -> Result<(Vec<HopLog>, Vec<String>, u64, LlmResponse)> {
         let mut hops: Vec<HopLog> = Vec::new();
         let mut accumulated_context: Vec<String> = Vec::new();
 
@@ -54,6 +188,14 @@ impl Agent {
         let plan_start = Instant::now();
         let (queries, plan_response) = self.planner.plan(question).await?;
         let plan_latency = plan_start.elapsed().as_millis() as u64;
+        Metrics::global().record_llm(
+            Component::Planner,
+            &self.config.planner_model,
+            plan_latency,
+            plan_response.input_tokens,
+            plan_response.output_tokens,
+            plan_response.cost,
+        );
 
         if verbose {
             eprintln!(
@@ -75,14 +217,57 @@ impl Agent {
 
             let hop_start = Instant::now();
 
-            // Search Qdrant (cloud inference handles embedding server-side)
-            let search_start = Instant::now();
             let query_text = pending_queries.join(" ");
-            let passages = self
-                .retriever
-                .search(&query_text, self.config.top_k)
-                .await?;
-            let search_latency = search_start.elapsed().as_millis() as u64;
+
+            // Embed client-side if configured, otherwise let Qdrant embed server-side. The
+            // cache needs whichever vector the retriever would use, so this runs up front
+            // rather than only on a cache miss.
+            let embed_start = Instant::now();
+            let vector = match &self.embedder {
+                Some(embedder) => Some(
+                    embedder
+                        .embed(&[query_text.as_str()])
+                        .await?
+                        .into_iter()
+                        .next()
+                        .context("Embedder returned no vectors")?,
+                ),
+                None => None,
+            };
+            let embedding_latency = embed_start.elapsed().as_millis() as u64;
+
+            // Check the semantic cache before paying for a search again.
+            let cached = match &self.cache {
+                Some(cache) => cache.get(&query_text, vector.as_deref()).await?,
+                None => None,
+            };
+            let cache_hit = cached.is_some();
+
+            let (passages, search_retries, search_latency) = if let Some(passages) = cached {
+                (passages, Default::default(), 0)
+            } else {
+                let search_start = Instant::now();
+                let (passages, search_retries) = match vector.clone() {
+                    Some(vector) => {
+                        self.retriever
+                            .search_with_vector(vector, self.config.top_k)
+                            .await?
+                    }
+                    None => {
+                        self.retriever
+                            .search(&query_text, self.config.top_k)
+                            .await?
+                    }
+                };
+                let search_latency = search_start.elapsed().as_millis() as u64;
+                Metrics::global().record_search(search_latency);
+
+                if let Some(cache) = &self.cache {
+                    cache.put(&query_text, vector.as_deref(), &passages).await?;
+                }
+
+                (passages, search_retries, search_latency)
+            };
             let num_results = passages.len();
 
             let passage_texts: Vec<String> = passages.iter().map(|p| p.text.clone()).collect();
@@ -97,11 +282,19 @@ impl Agent {
                 .read(question, &passage_texts, &accumulated_context)
                 .await?;
             let llm_latency = llm_start.elapsed().as_millis() as u64;
+            Metrics::global().record_llm(
+                Component::Reader,
+                &self.config.reader_model,
+                llm_latency,
+                reader_response.input_tokens,
+                reader_response.output_tokens,
+                reader_response.cost,
+            );
 
             let hop_log = HopLog {
                 hop_number: hop_number as u32,
                 queries: pending_queries.clone(),
-                embedding_latency_ms: 0,
+                embedding_latency_ms: embedding_latency,
                 search_latency_ms: search_latency,
                 num_results: num_results as u32,
                 tokens_in_passages,
@@ -116,6 +309,9 @@ impl Agent {
                     ReaderDecision::Synthesize => "synthesize".into(),
                 },
                 total_hop_latency_ms: hop_start.elapsed().as_millis() as u64,
+                retries: search_retries.retries + reader_response.retries,
+                retry_wait_ms: search_retries.wait_ms + reader_response.retry_wait_ms,
+                cache_hit,
             };
 
             if verbose {
@@ -135,18 +331,36 @@ impl Agent {
             }
         }
 
-        // Synthesize final answer
-        let synth_start = Instant::now();
-        let (answer, synth_response) = self
-            .synthesizer
-            .synthesize(question, &accumulated_context)
-            .await?;
-        let synth_latency = synth_start.elapsed().as_millis() as u64;
+        Ok((hops, accumulated_context, plan_latency, plan_response))
+    }
 
-        if verbose {
-            eprintln!("[synthesizer] Generated answer in {}ms", synth_latency);
-        }
+    /// Records the synthesizer's latency/tokens/cost against the metrics registry. Shared by
+    /// [`ask`](Self::ask) and [`ask_streaming`](Self::ask_streaming).
+    fn record_synthesis_metrics(&self, synth_latency: u64, synth_response: &LlmResponse) {
+        Metrics::global().record_llm(
+            Component::Synthesizer,
+            &self.config.synthesizer_model,
+            synth_latency,
+            synth_response.input_tokens,
+            synth_response.output_tokens,
+            synth_response.cost,
+        );
+    }
 
+    /// Assembles the [`RunLog`] from the planner/hop/synthesizer results, shared by
+    /// [`ask`](Self::ask) and [`ask_streaming`](Self::ask_streaming).
+    #[allow(clippy::too_many_arguments)]
+    fn build_run_log(
+        &self,
+        question: &str,
+        run_start: Instant,
+        hops: Vec<HopLog>,
+        plan_latency: u64,
+        plan_response: &LlmResponse,
+        synth_latency: u64,
+        synth_response: &LlmResponse,
+        answer: String,
+    ) -> RunLog {
         let total_latency = run_start.elapsed().as_millis() as u64;
         let total_llm_input_tokens: u32 = plan_response.input_tokens
             + synth_response.input_tokens
@@ -157,11 +371,11 @@ impl Agent {
         let total_cost: f64 =
             plan_response.cost + synth_response.cost + hops.iter().map(|h| h.llm_cost).sum::<f64>();
 
-        let run_log = RunLog {
+        RunLog {
             id: uuid::Uuid::new_v4().to_string(),
             timestamp: chrono::Utc::now().to_rfc3339(),
             question: question.to_string(),
-            hops: hops.clone(),
+            hops,
             synthesis_latency_ms: synth_latency,
             synthesis_input_tokens: synth_response.input_tokens,
             synthesis_output_tokens: synth_response.output_tokens,
@@ -173,10 +387,68 @@ impl Agent {
             total_llm_output_tokens,
             total_cost,
             final_answer: answer,
-        };
+        }
+    }
+}
 
-        self.logger.write(&run_log)?;
+/// Construct the LLM provider selected for a given role ("openai" or "anthropic"), as chosen by
+/// `PLANNER_PROVIDER`/`READER_PROVIDER`/`SYNTHESIZER_PROVIDER`, each falling back to the
+/// crate-wide `LLM_PROVIDER` default when unset.
+fn build_provider(role_provider: &str, config: &Config) -> Result<Arc<dyn LlmProvider>> {
+    match role_provider {
+        "anthropic" => {
+            let api_key = config
+                .anthropic_api_key
+                .as_deref()
+                .context("ANTHROPIC_API_KEY must be set to use the anthropic provider")?;
+            Ok(Arc::new(AnthropicClient::new(api_key)))
+        }
+        "openai" => Ok(Arc::new(LlmClient::new(
+            &config.llm_api_key,
+            &config.llm_base_url,
+            config.max_retries,
+        ))),
+        other => anyhow::bail!("Unsupported provider: {}", other),
+    }
+}
 
-        Ok(run_log)
+/// Construct the run log store selected by `RUN_LOG_BACKEND` ("jsonl" or "sqlite").
+fn build_run_store(config: &Config) -> Result<Box<dyn RunStore>> {
+    match config.run_log_backend.as_str() {
+        "jsonl" => Ok(Box::new(RunLogger::new("logs")?)),
+        "sqlite" => Ok(Box::new(SqliteRunLogger::new(DEFAULT_DB_PATH)?)),
+        other => anyhow::bail!("Unsupported RUN_LOG_BACKEND: {}", other),
+    }
+}
+
+/// Construct the client-side embedder selected by `EMBEDDING_PROVIDER` ("fastembed", "openai",
+/// or "ollama").
+fn build_embedder(config: &Config) -> Result<Arc<dyn Embedder>> {
+    match config.embedding_provider.as_str() {
+        "fastembed" => Ok(Arc::new(FastEmbedLocal::new(&config.embedding_model)?)),
+        "openai" => Ok(Arc::new(OpenAiEmbedder::new(
+            &config.llm_api_key,
+            "https://api.openai.com/v1/embeddings",
+            &config.embedding_model,
+            embedding_dimensions(&config.embedding_model),
+        ))),
+        "ollama" => Ok(Arc::new(OllamaEmbedder::new(
+            "http://localhost:11434",
+            &config.embedding_model,
+            embedding_dimensions(&config.embedding_model),
+        ))),
+        other => anyhow::bail!("Unsupported EMBEDDING_PROVIDER: {}", other),
+    }
+}
+
+/// Best-effort dimensionality for well-known hosted embedding models, handed to the hosted
+/// embedders so their `dimensions()` can report it without a request. Unknown models get 1024.
+fn embedding_dimensions(model_name: &str) -> usize {
+    match model_name {
+        "text-embedding-3-small" => 1536,
+        "text-embedding-3-large" => 3072,
+        "text-embedding-ada-002" => 1536,
+        "nomic-embed-text" => 768,
+        _ => 1024,
     }
 }
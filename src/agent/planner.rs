@@ -1,6 +1,7 @@
 use anyhow::Result;
+use std::sync::Arc;
 
-use crate::llm::{LlmClient, LlmResponse};
+use crate::llm::{LlmProvider, LlmResponse};
 
 const SYSTEM_PROMPT: &str = r#"You are a research query planner. Given a complex question, decompose it into 1-4 specific search queries that would help find relevant information. Each query should target a different aspect of the question.
 
@@ -10,12 +11,12 @@ Respond with ONLY a JSON array of query strings. Example:
 Do not include any other text, explanation, or formatting."#;
 
 pub struct Planner {
-    llm: LlmClient,
+    llm: Arc<dyn LlmProvider>,
     model: String,
 }
 
 impl Planner {
-    pub fn new(llm: LlmClient, model: String) -> Self {
+    pub fn new(llm: Arc<dyn LlmProvider>, model: String) -> Self {
         Self { llm, model }
     }
 
@@ -1,23 +1,21 @@
 use anyhow::Result;
+use serde_json::json;
+use std::sync::Arc;
 
-use crate::llm::{AnthropicClient, LlmResponse};
+use crate::llm::{LlmProvider, LlmResponse, Tool};
 
 const SYSTEM_PROMPT: &str = r#"You are a research reader. You are given a question, retrieved passages, and context accumulated from previous research hops.
 
-Your job is to decide:
-1. If you have enough information to answer the question, respond with:
-   {"decision": "synthesize"}
-
-2. If you need more information, respond with:
-   {"decision": "continue", "follow_up_queries": ["query 1", "query 2"]}
-   Provide 1-3 follow-up queries targeting specific gaps in your knowledge.
+Decide whether you have enough information to answer the question:
+- If not, call `continue_research` with 1-3 follow-up queries targeting specific gaps in your knowledge.
+- If you do, call `synthesize`.
 
 Consider:
 - What aspects of the question remain unanswered?
 - What new leads do the passages suggest?
 - Are there connections between passages that need more investigation?
 
-Respond with ONLY the JSON object. No other text."#;
+Always respond by calling exactly one of the two tools."#;
 
 #[derive(Debug)]
 pub enum ReaderDecision {
@@ -25,19 +23,13 @@ pub enum ReaderDecision {
     Synthesize,
 }
 
-#[derive(serde::Deserialize)]
-struct ReaderOutput {
-    decision: String,
-    follow_up_queries: Option<Vec<String>>,
-}
-
 pub struct Reader {
-    llm: AnthropicClient,
+    llm: Arc<dyn LlmProvider>,
     model: String,
 }
 
 impl Reader {
-    pub fn new(llm: AnthropicClient, model: String) -> Self {
+    pub fn new(llm: Arc<dyn LlmProvider>, model: String) -> Self {
         Self { llm, model }
     }
 
@@ -85,41 +77,73 @@ impl Reader {
 
         let response = self
             .llm
-            .complete(&self.model, Some(SYSTEM_PROMPT), &user_message)
+            .complete_with_tools(&self.model, Some(SYSTEM_PROMPT), &user_message, &tools(), None)
             .await?;
 
-        let decision = parse_decision(&response.text);
+        let decision = decide(&response);
 
         Ok((decision, response))
     }
 }
 
-fn parse_decision(text: &str) -> ReaderDecision {
-    // Try to parse the JSON response
-    let json_str = if let Some(start) = text.find('{') {
-        if let Some(end) = text.rfind('}') {
-            &text[start..=end]
-        } else {
-            text
-        }
-    } else {
-        text
-    };
-
-    if let Ok(output) = serde_json::from_str::<ReaderOutput>(json_str) {
-        if output.decision == "continue" {
-            if let Some(queries) = output.follow_up_queries {
-                if !queries.is_empty() {
-                    return ReaderDecision::Continue {
-                        follow_up_queries: queries,
-                    };
+fn tools() -> [Tool; 2] {
+    [
+        Tool {
+            name: "continue_research".to_string(),
+            description: "Request another round of retrieval before answering the question."
+                .to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "follow_up_queries": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "1-3 search queries targeting specific gaps in the research so far",
+                    },
+                },
+                "required": ["follow_up_queries"],
+            }),
+        },
+        Tool {
+            name: "synthesize".to_string(),
+            description: "Signal that enough information has been gathered to answer the question."
+                .to_string(),
+            parameters: json!({ "type": "object", "properties": {} }),
+        },
+    ]
+}
+
+/// Merge every `continue_research` call in the hop into one follow-up query list, letting the
+/// model fan out several searches in a single turn instead of one query per hop.
+fn decide(response: &LlmResponse) -> ReaderDecision {
+    let mut follow_up_queries = Vec::new();
+
+    for call in &response.tool_calls {
+        match call.name.as_str() {
+            "continue_research" => {
+                if let Some(queries) = call
+                    .arguments
+                    .get("follow_up_queries")
+                    .and_then(|v| v.as_array())
+                {
+                    follow_up_queries.extend(
+                        queries
+                            .iter()
+                            .filter_map(|q| q.as_str())
+                            .map(|q| q.to_string()),
+                    );
                 }
             }
+            "synthesize" => return ReaderDecision::Synthesize,
+            _ => {}
         }
     }
 
-    // Default to synthesize if parsing fails or decision is "synthesize"
-    ReaderDecision::Synthesize
+    if follow_up_queries.is_empty() {
+        ReaderDecision::Synthesize
+    } else {
+        ReaderDecision::Continue { follow_up_queries }
+    }
 }
 
 fn truncate(s: &str, max_chars: usize) -> &str {
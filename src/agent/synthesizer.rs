@@ -1,6 +1,8 @@
 use anyhow::Result;
+use futures::Stream;
+use std::sync::Arc;
 
-use crate::llm::{LlmClient, LlmResponse};
+use crate::llm::{LlmProvider, LlmResponse, StreamChunk};
 
 const SYSTEM_PROMPT: &str = r#"You are a research synthesizer. Given a question and accumulated research context (passages retrieved across multiple search hops), provide a comprehensive, well-structured answer.
 
@@ -12,12 +14,12 @@ Guidelines:
 - Keep the answer focused and concise (2-4 paragraphs)"#;
 
 pub struct Synthesizer {
-    llm: LlmClient,
+    llm: Arc<dyn LlmProvider>,
     model: String,
 }
 
 impl Synthesizer {
-    pub fn new(llm: LlmClient, model: String) -> Self {
+    pub fn new(llm: Arc<dyn LlmProvider>, model: String) -> Self {
         Self { llm, model }
     }
 
@@ -26,6 +28,35 @@ impl Synthesizer {
         question: &str,
         accumulated_context: &[String],
     ) -> Result<(String, LlmResponse)> {
+        let user_message = Self::user_message(question, accumulated_context);
+
+        let response = self
+            .llm
+            .complete(&self.model, Some(SYSTEM_PROMPT), &user_message)
+            .await?;
+
+        Ok((response.text.clone(), response))
+    }
+
+    /// Like [`synthesize`](Self::synthesize), but streams the answer as it's generated
+    /// instead of blocking until the full response is ready.
+    pub fn synthesize_stream<'a>(
+        &'a self,
+        question: &'a str,
+        accumulated_context: &'a [String],
+    ) -> impl Stream<Item = Result<StreamChunk>> + 'a {
+        let user_message = Self::user_message(question, accumulated_context);
+        async_stream::try_stream! {
+            use futures::StreamExt;
+
+            let mut stream = self.llm.complete_stream(&self.model, Some(SYSTEM_PROMPT), &user_message);
+            while let Some(chunk) = stream.next().await {
+                yield chunk?;
+            }
+        }
+    }
+
+    fn user_message(question: &str, accumulated_context: &[String]) -> String {
         let context_text = accumulated_context
             .iter()
             .enumerate()
@@ -33,16 +64,9 @@ impl Synthesizer {
             .collect::<Vec<_>>()
             .join("\n\n");
 
-        let user_message = format!(
+        format!(
             "Question: {}\n\nResearch Context:\n{}",
             question, context_text
-        );
-
-        let response = self
-            .llm
-            .complete(&self.model, Some(SYSTEM_PROMPT), &user_message)
-            .await?;
-
-        Ok((response.text.clone(), response))
+        )
     }
 }
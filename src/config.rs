@@ -4,32 +4,60 @@ use anyhow::{Context, Result};
 pub struct Config {
     pub llm_api_key: String,
     pub llm_base_url: String,
+    pub anthropic_api_key: Option<String>,
+    pub llm_provider: String,
+    pub planner_provider: String,
+    pub reader_provider: String,
+    pub synthesizer_provider: String,
     pub qdrant_url: String,
     pub qdrant_api_key: Option<String>,
     pub qdrant_collection: String,
+    pub qdrant_cache_collection: Option<String>,
+    pub semantic_cache_threshold: f32,
     pub planner_model: String,
     pub reader_model: String,
     pub synthesizer_model: String,
     pub embedding_model: String,
+    pub embedding_provider: String,
     pub cloud_inference: bool,
     pub max_hops: usize,
     pub top_k: u64,
+    pub max_retries: u32,
+    pub run_log_backend: String,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self> {
         dotenvy::dotenv().ok();
 
+        // Default backend for any role that doesn't set its own *_PROVIDER override. Since
+        // `LlmClient` only assumes an OpenAI-compatible `/chat/completions` shape, pointing
+        // `LLM_BASE_URL` at a self-hosted TGI/vLLM/Ollama server works the same as OpenRouter.
+        let llm_provider = std::env::var("LLM_PROVIDER").unwrap_or_else(|_| "openai".into());
+
         Ok(Self {
             llm_api_key: std::env::var("LLM_API_KEY")
                 .context("LLM_API_KEY must be set")?,
             llm_base_url: std::env::var("LLM_BASE_URL")
                 .unwrap_or_else(|_| "https://openrouter.ai/api/v1/chat/completions".into()),
+            anthropic_api_key: std::env::var("ANTHROPIC_API_KEY").ok(),
+            planner_provider: std::env::var("PLANNER_PROVIDER")
+                .unwrap_or_else(|_| llm_provider.clone()),
+            reader_provider: std::env::var("READER_PROVIDER")
+                .unwrap_or_else(|_| llm_provider.clone()),
+            synthesizer_provider: std::env::var("SYNTHESIZER_PROVIDER")
+                .unwrap_or_else(|_| llm_provider.clone()),
+            llm_provider,
             qdrant_url: std::env::var("QDRANT_URL")
                 .unwrap_or_else(|_| "http://localhost:6334".into()),
             qdrant_api_key: std::env::var("QDRANT_API_KEY").ok(),
             qdrant_collection: std::env::var("QDRANT_COLLECTION")
                 .unwrap_or_else(|_| "wiki_passages".into()),
+            qdrant_cache_collection: std::env::var("QDRANT_CACHE_COLLECTION").ok(),
+            semantic_cache_threshold: std::env::var("SEMANTIC_CACHE_THRESHOLD")
+                .unwrap_or_else(|_| "0.95".into())
+                .parse()
+                .context("SEMANTIC_CACHE_THRESHOLD must be a number")?,
             planner_model: std::env::var("PLANNER_MODEL")
                 .unwrap_or_else(|_| "anthropic/claude-haiku-4-5-20241022".into()),
             reader_model: std::env::var("READER_MODEL")
@@ -38,6 +66,8 @@ impl Config {
                 .unwrap_or_else(|_| "anthropic/claude-sonnet-4-20250514".into()),
             embedding_model: std::env::var("EMBEDDING_MODEL")
                 .unwrap_or_else(|_| "mixedbread-ai/mxbai-embed-large-v1".into()),
+            embedding_provider: std::env::var("EMBEDDING_PROVIDER")
+                .unwrap_or_else(|_| "fastembed".into()),
             cloud_inference: std::env::var("CLOUD_INFERENCE")
                 .unwrap_or_else(|_| "true".into())
                 .parse()
@@ -50,6 +80,12 @@ impl Config {
                 .unwrap_or_else(|_| "10".into())
                 .parse()
                 .context("TOP_K must be a number")?,
+            max_retries: std::env::var("MAX_RETRIES")
+                .unwrap_or_else(|_| "3".into())
+                .parse()
+                .context("MAX_RETRIES must be a number")?,
+            run_log_backend: std::env::var("RUN_LOG_BACKEND")
+                .unwrap_or_else(|_| "jsonl".into()),
         })
     }
 }
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
 use std::path::PathBuf;
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HopLog {
@@ -18,6 +19,9 @@ pub struct HopLog {
     pub llm_cost: f64,
     pub decision: String,
     pub total_hop_latency_ms: u64,
+    pub retries: u32,
+    pub retry_wait_ms: u64,
+    pub cache_hit: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,26 +65,32 @@ impl RunLog {
     }
 }
 
+/// Appends one JSON line per run to `<dir>/runs.jsonl`. The file handle is held open behind a
+/// `Mutex`, like `SqliteRunLogger` holds its `Connection`, so concurrent `ask()` calls (e.g.
+/// `eval --concurrency`) serialize their writes instead of interleaving partial lines and
+/// corrupting the file for later readers.
 pub struct RunLogger {
-    dir: PathBuf,
+    file: Mutex<fs::File>,
 }
 
 impl RunLogger {
     pub fn new(dir: &str) -> Result<Self> {
         let dir = PathBuf::from(dir);
         fs::create_dir_all(&dir).context("Failed to create logs directory")?;
-        Ok(Self { dir })
-    }
-
-    pub fn write(&self, run_log: &RunLog) -> Result<()> {
-        let path = self.dir.join("runs.jsonl");
-        let mut file = fs::OpenOptions::new()
+        let file = fs::OpenOptions::new()
             .create(true)
             .append(true)
-            .open(&path)
+            .open(dir.join("runs.jsonl"))
             .context("Failed to open log file")?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
 
+    pub fn write(&self, run_log: &RunLog) -> Result<()> {
         let json = serde_json::to_string(run_log).context("Failed to serialize run log")?;
+
+        let mut file = self.file.lock().unwrap();
         writeln!(file, "{}", json).context("Failed to write log")?;
 
         Ok(())
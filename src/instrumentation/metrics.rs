@@ -0,0 +1,186 @@
+use std::sync::OnceLock;
+
+use prometheus::{
+    exponential_buckets, HistogramVec, IntCounter, IntCounterVec, IntGauge, Registry, TextEncoder,
+};
+
+/// Names the three LLM-calling stages, used as the `component` label on per-model metrics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Component {
+    Planner,
+    Reader,
+    Synthesizer,
+}
+
+impl Component {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Component::Planner => "planner",
+            Component::Reader => "reader",
+            Component::Synthesizer => "synthesizer",
+        }
+    }
+}
+
+/// Prometheus counters and histograms scraped by `--serve-metrics`, registered once and
+/// incremented inline in [`Agent::ask`](crate::agent::Agent::ask) where the numbers are
+/// already computed. Kept separate from [`RunLog`](super::RunLog)/[`HopLog`](super::HopLog),
+/// which record per-run detail for `history`; this module only tracks live aggregates.
+pub struct Metrics {
+    registry: Registry,
+    runs_total: IntCounter,
+    hops_per_run: prometheus::Histogram,
+    search_latency_ms: prometheus::Histogram,
+    llm_latency_ms: HistogramVec,
+    llm_input_tokens: IntCounterVec,
+    llm_output_tokens: IntCounterVec,
+    llm_cost_usd: prometheus::CounterVec,
+    in_flight_runs: IntGauge,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let runs_total = IntCounter::new("agentic_search_runs_total", "Completed `ask` runs")
+            .expect("metric definition is valid");
+        let hops_per_run = prometheus::Histogram::with_opts(
+            prometheus::HistogramOpts::new(
+                "agentic_search_hops_per_run",
+                "Number of reader hops before synthesis",
+            )
+            .buckets(vec![1.0, 2.0, 3.0, 4.0, 5.0, 7.0, 10.0, 15.0]),
+        )
+        .expect("metric definition is valid");
+        let search_latency_ms = prometheus::Histogram::with_opts(
+            prometheus::HistogramOpts::new(
+                "agentic_search_retrieval_latency_ms",
+                "Qdrant search latency per hop, in milliseconds",
+            )
+            .buckets(exponential_buckets(10.0, 2.0, 12).expect("valid buckets")),
+        )
+        .expect("metric definition is valid");
+        let llm_latency_ms = HistogramVec::new(
+            prometheus::HistogramOpts::new(
+                "agentic_search_llm_latency_ms",
+                "LLM call latency, in milliseconds",
+            )
+            .buckets(exponential_buckets(50.0, 2.0, 12).expect("valid buckets")),
+            &["component", "model"],
+        )
+        .expect("metric definition is valid");
+        let llm_input_tokens = IntCounterVec::new(
+            prometheus::Opts::new(
+                "agentic_search_llm_input_tokens_total",
+                "Cumulative LLM input tokens",
+            ),
+            &["component", "model"],
+        )
+        .expect("metric definition is valid");
+        let llm_output_tokens = IntCounterVec::new(
+            prometheus::Opts::new(
+                "agentic_search_llm_output_tokens_total",
+                "Cumulative LLM output tokens",
+            ),
+            &["component", "model"],
+        )
+        .expect("metric definition is valid");
+        let llm_cost_usd = prometheus::CounterVec::new(
+            prometheus::Opts::new(
+                "agentic_search_llm_cost_usd_total",
+                "Cumulative LLM cost in USD, as reported by the provider",
+            ),
+            &["component", "model"],
+        )
+        .expect("metric definition is valid");
+        let in_flight_runs = IntGauge::new(
+            "agentic_search_in_flight_runs",
+            "Runs that have started but not yet completed",
+        )
+        .expect("metric definition is valid");
+
+        for collector in [
+            Box::new(runs_total.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(hops_per_run.clone()),
+            Box::new(search_latency_ms.clone()),
+            Box::new(llm_latency_ms.clone()),
+            Box::new(llm_input_tokens.clone()),
+            Box::new(llm_output_tokens.clone()),
+            Box::new(llm_cost_usd.clone()),
+            Box::new(in_flight_runs.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric name registered exactly once");
+        }
+
+        Self {
+            registry,
+            runs_total,
+            hops_per_run,
+            search_latency_ms,
+            llm_latency_ms,
+            llm_input_tokens,
+            llm_output_tokens,
+            llm_cost_usd,
+            in_flight_runs,
+        }
+    }
+
+    /// The process-wide registry. Metrics are only ever incremented through this instance, so
+    /// `ask` and the `/metrics` HTTP handler always see the same counters.
+    pub fn global() -> &'static Metrics {
+        static METRICS: OnceLock<Metrics> = OnceLock::new();
+        METRICS.get_or_init(Metrics::new)
+    }
+
+    pub fn run_started(&self) {
+        self.in_flight_runs.inc();
+    }
+
+    pub fn run_completed(&self, num_hops: usize) {
+        self.in_flight_runs.dec();
+        self.runs_total.inc();
+        self.hops_per_run.observe(num_hops as f64);
+    }
+
+    pub fn record_search(&self, latency_ms: u64) {
+        self.search_latency_ms.observe(latency_ms as f64);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn record_llm(
+        &self,
+        component: Component,
+        model: &str,
+        latency_ms: u64,
+        input_tokens: u32,
+        output_tokens: u32,
+        cost: f64,
+    ) {
+        let component = component.as_str();
+        self.llm_latency_ms
+            .with_label_values(&[component, model])
+            .observe(latency_ms as f64);
+        self.llm_input_tokens
+            .with_label_values(&[component, model])
+            .inc_by(input_tokens as u64);
+        self.llm_output_tokens
+            .with_label_values(&[component, model])
+            .inc_by(output_tokens as u64);
+        self.llm_cost_usd
+            .with_label_values(&[component, model])
+            .inc_by(cost);
+    }
+
+    /// Render the current state of every registered collector in Prometheus text exposition
+    /// format, ready to write straight into an HTTP response body.
+    pub fn encode(&self) -> String {
+        let families = self.registry.gather();
+        let mut buf = Vec::new();
+        TextEncoder::new()
+            .encode(&families, &mut buf)
+            .expect("encoding to an in-memory buffer cannot fail");
+        String::from_utf8(buf).expect("prometheus text format is always valid UTF-8")
+    }
+}
@@ -0,0 +1,58 @@
+use anyhow::{Context, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use super::Metrics;
+
+/// Serves `GET /metrics` in Prometheus text exposition format on `port`, backed by the global
+/// [`Metrics`] registry. Runs until the process exits; callers spawn it as a background task
+/// via `--serve-metrics PORT` so a long `eval` run can be watched on a dashboard as it goes,
+/// rather than only via the final summary.
+///
+/// This is deliberately a hand-rolled request line parser rather than a full HTTP server
+/// framework — the endpoint only ever needs to answer one route with one response shape.
+pub async fn serve(port: u16) -> Result<()> {
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .await
+        .context(format!("Failed to bind metrics server to port {}", port))?;
+
+    tracing::info!("Metrics server listening on http://0.0.0.0:{}/metrics", port);
+
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(&mut stream).await {
+                tracing::warn!("Metrics server connection error: {}", err);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: &mut tokio::net::TcpStream) -> Result<()> {
+    // The request body (if any) is irrelevant to a metrics scrape, so we only need enough of
+    // the request line to tell a `/metrics` GET apart from anything else.
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request_line = String::from_utf8_lossy(&buf[..n]);
+    let path = request_line.split_whitespace().nth(1).unwrap_or("/");
+
+    let response = if path == "/metrics" {
+        let body = Metrics::global().encode();
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    } else {
+        let body = "not found";
+        format!(
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        )
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await?;
+    Ok(())
+}
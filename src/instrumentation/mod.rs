@@ -0,0 +1,28 @@
+pub mod logger;
+pub mod metrics;
+pub mod metrics_server;
+pub mod sqlite;
+
+pub use logger::{HopLog, RunLog, RunLogger};
+pub use metrics::{Component, Metrics};
+pub use sqlite::{RunStats, RunSummary, SqliteRunLogger, DEFAULT_DB_PATH};
+
+use anyhow::Result;
+
+/// A place to persist completed [`RunLog`]s. `RunLogger` appends JSONL; `SqliteRunLogger`
+/// stores the same data in a queryable database instead, selected via `RUN_LOG_BACKEND`.
+pub trait RunStore: Send + Sync {
+    fn write(&self, run_log: &RunLog) -> Result<()>;
+}
+
+impl RunStore for RunLogger {
+    fn write(&self, run_log: &RunLog) -> Result<()> {
+        RunLogger::write(self, run_log)
+    }
+}
+
+impl RunStore for SqliteRunLogger {
+    fn write(&self, run_log: &RunLog) -> Result<()> {
+        SqliteRunLogger::write(self, run_log)
+    }
+}
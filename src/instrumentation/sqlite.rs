@@ -0,0 +1,291 @@
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::Mutex;
+
+use super::logger::{HopLog, RunLog};
+
+/// Default database path when `RUN_LOG_BACKEND=sqlite`, and what `history` reads from.
+pub const DEFAULT_DB_PATH: &str = "logs/runs.db";
+
+/// Lightweight projection of a [`RunLog`] used by `history list`, without pulling in its hops.
+#[derive(Debug, Clone)]
+pub struct RunSummary {
+    pub id: String,
+    pub timestamp: String,
+    pub question: String,
+    pub num_hops: u32,
+    pub total_latency_ms: u64,
+    pub total_cost: f64,
+}
+
+/// Aggregate stats over a set of runs, computed in SQL rather than recomputed in memory.
+#[derive(Debug, Clone)]
+pub struct RunStats {
+    pub num_runs: u64,
+    pub avg_hops: f64,
+    pub avg_latency_ms: f64,
+    pub total_cost: f64,
+}
+
+/// Persists [`RunLog`]s to a SQLite database instead of the append-only JSONL file, so past
+/// runs can be listed, inspected by id, and aggregated with SQL rather than by replaying
+/// `runs.jsonl` into memory.
+pub struct SqliteRunLogger {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteRunLogger {
+    pub fn new(path: &str) -> Result<Self> {
+        if let Some(parent) = std::path::Path::new(path).parent() {
+            std::fs::create_dir_all(parent).context("Failed to create run log directory")?;
+        }
+        let conn = Connection::open(path).context("Failed to open run log database")?;
+
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS runs (
+                id                       TEXT PRIMARY KEY,
+                timestamp                TEXT NOT NULL,
+                question                 TEXT NOT NULL,
+                plan_latency_ms          INTEGER NOT NULL,
+                plan_input_tokens        INTEGER NOT NULL,
+                plan_output_tokens       INTEGER NOT NULL,
+                synthesis_latency_ms     INTEGER NOT NULL,
+                synthesis_input_tokens   INTEGER NOT NULL,
+                synthesis_output_tokens  INTEGER NOT NULL,
+                total_latency_ms         INTEGER NOT NULL,
+                total_llm_input_tokens   INTEGER NOT NULL,
+                total_llm_output_tokens  INTEGER NOT NULL,
+                total_cost               REAL NOT NULL,
+                final_answer             TEXT NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS hops (
+                run_id                TEXT NOT NULL REFERENCES runs(id),
+                hop_number            INTEGER NOT NULL,
+                queries_json          TEXT NOT NULL,
+                embedding_latency_ms  INTEGER NOT NULL,
+                search_latency_ms     INTEGER NOT NULL,
+                num_results           INTEGER NOT NULL,
+                tokens_in_passages    INTEGER NOT NULL,
+                llm_latency_ms        INTEGER NOT NULL,
+                llm_input_tokens      INTEGER NOT NULL,
+                llm_output_tokens     INTEGER NOT NULL,
+                llm_cost              REAL NOT NULL,
+                decision              TEXT NOT NULL,
+                total_hop_latency_ms  INTEGER NOT NULL,
+                retries               INTEGER NOT NULL,
+                retry_wait_ms         INTEGER NOT NULL,
+                cache_hit             INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS hops_run_id ON hops(run_id);",
+        )
+        .context("Failed to create run log tables")?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn write(&self, run_log: &RunLog) -> Result<()> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction().context("Failed to start run log transaction")?;
+
+        tx.execute(
+            "INSERT INTO runs (
+                id, timestamp, question,
+                plan_latency_ms, plan_input_tokens, plan_output_tokens,
+                synthesis_latency_ms, synthesis_input_tokens, synthesis_output_tokens,
+                total_latency_ms, total_llm_input_tokens, total_llm_output_tokens,
+                total_cost, final_answer
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14)",
+            params![
+                run_log.id,
+                run_log.timestamp,
+                run_log.question,
+                run_log.plan_latency_ms,
+                run_log.plan_input_tokens,
+                run_log.plan_output_tokens,
+                run_log.synthesis_latency_ms,
+                run_log.synthesis_input_tokens,
+                run_log.synthesis_output_tokens,
+                run_log.total_latency_ms,
+                run_log.total_llm_input_tokens,
+                run_log.total_llm_output_tokens,
+                run_log.total_cost,
+                run_log.final_answer,
+            ],
+        )
+        .context("Failed to insert run row")?;
+
+        for hop in &run_log.hops {
+            let queries_json =
+                serde_json::to_string(&hop.queries).context("Failed to serialize hop queries")?;
+            tx.execute(
+                "INSERT INTO hops (
+                    run_id, hop_number, queries_json,
+                    embedding_latency_ms, search_latency_ms, num_results, tokens_in_passages,
+                    llm_latency_ms, llm_input_tokens, llm_output_tokens, llm_cost,
+                    decision, total_hop_latency_ms, retries, retry_wait_ms, cache_hit
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                params![
+                    run_log.id,
+                    hop.hop_number,
+                    queries_json,
+                    hop.embedding_latency_ms,
+                    hop.search_latency_ms,
+                    hop.num_results,
+                    hop.tokens_in_passages,
+                    hop.llm_latency_ms,
+                    hop.llm_input_tokens,
+                    hop.llm_output_tokens,
+                    hop.llm_cost,
+                    hop.decision,
+                    hop.total_hop_latency_ms,
+                    hop.retries,
+                    hop.retry_wait_ms,
+                    hop.cache_hit,
+                ],
+            )
+            .context("Failed to insert hop row")?;
+        }
+
+        tx.commit().context("Failed to commit run log transaction")?;
+        Ok(())
+    }
+
+    /// The most recent `limit` runs, newest first.
+    pub fn list_recent(&self, limit: u32) -> Result<Vec<RunSummary>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn
+            .prepare(
+                "SELECT runs.id, runs.timestamp, runs.question, runs.total_latency_ms, runs.total_cost,
+                        (SELECT COUNT(*) FROM hops WHERE hops.run_id = runs.id) AS num_hops
+                 FROM runs
+                 ORDER BY runs.timestamp DESC
+                 LIMIT ?1",
+            )
+            .context("Failed to prepare history list query")?;
+
+        let rows = stmt
+            .query_map(params![limit], |row| {
+                Ok(RunSummary {
+                    id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    question: row.get(2)?,
+                    total_latency_ms: row.get(3)?,
+                    total_cost: row.get(4)?,
+                    num_hops: row.get(5)?,
+                })
+            })
+            .context("Failed to run history list query")?;
+
+        rows.collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read history list rows")
+    }
+
+    /// The full `RunLog`, hops included, for a single run id.
+    pub fn get(&self, id: &str) -> Result<Option<RunLog>> {
+        let conn = self.conn.lock().unwrap();
+
+        let run_log = conn
+            .query_row(
+                "SELECT id, timestamp, question,
+                        plan_latency_ms, plan_input_tokens, plan_output_tokens,
+                        synthesis_latency_ms, synthesis_input_tokens, synthesis_output_tokens,
+                        total_latency_ms, total_llm_input_tokens, total_llm_output_tokens,
+                        total_cost, final_answer
+                 FROM runs WHERE id = ?1",
+                params![id],
+                |row| {
+                    Ok(RunLog {
+                        id: row.get(0)?,
+                        timestamp: row.get(1)?,
+                        question: row.get(2)?,
+                        hops: Vec::new(),
+                        plan_latency_ms: row.get(3)?,
+                        plan_input_tokens: row.get(4)?,
+                        plan_output_tokens: row.get(5)?,
+                        synthesis_latency_ms: row.get(6)?,
+                        synthesis_input_tokens: row.get(7)?,
+                        synthesis_output_tokens: row.get(8)?,
+                        total_latency_ms: row.get(9)?,
+                        total_llm_input_tokens: row.get(10)?,
+                        total_llm_output_tokens: row.get(11)?,
+                        total_cost: row.get(12)?,
+                        final_answer: row.get(13)?,
+                    })
+                },
+            )
+            .optional()
+            .context("Failed to query run row")?;
+
+        let Some(mut run_log) = run_log else {
+            return Ok(None);
+        };
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT hop_number, queries_json, embedding_latency_ms, search_latency_ms,
+                        num_results, tokens_in_passages, llm_latency_ms, llm_input_tokens,
+                        llm_output_tokens, llm_cost, decision, total_hop_latency_ms, retries,
+                        retry_wait_ms, cache_hit
+                 FROM hops WHERE run_id = ?1 ORDER BY hop_number ASC",
+            )
+            .context("Failed to prepare hops query")?;
+
+        let hops = stmt
+            .query_map(params![id], |row| {
+                let queries_json: String = row.get(1)?;
+                Ok(HopLog {
+                    hop_number: row.get(0)?,
+                    queries: serde_json::from_str(&queries_json).unwrap_or_default(),
+                    embedding_latency_ms: row.get(2)?,
+                    search_latency_ms: row.get(3)?,
+                    num_results: row.get(4)?,
+                    tokens_in_passages: row.get(5)?,
+                    llm_latency_ms: row.get(6)?,
+                    llm_input_tokens: row.get(7)?,
+                    llm_output_tokens: row.get(8)?,
+                    llm_cost: row.get(9)?,
+                    decision: row.get(10)?,
+                    total_hop_latency_ms: row.get(11)?,
+                    retries: row.get(12)?,
+                    retry_wait_ms: row.get(13)?,
+                    cache_hit: row.get(14)?,
+                })
+            })
+            .context("Failed to run hops query")?
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .context("Failed to read hop rows")?;
+
+        run_log.hops = hops;
+        Ok(Some(run_log))
+    }
+
+    /// Aggregate stats (run count, average hops/latency, total cost) over runs with a
+    /// timestamp `>= since` (an RFC 3339 string), or over everything if `since` is `None`.
+    pub fn stats(&self, since: Option<&str>) -> Result<RunStats> {
+        let conn = self.conn.lock().unwrap();
+
+        conn.query_row(
+            "SELECT COUNT(*),
+                    COALESCE(AVG(hop_counts.num_hops), 0.0),
+                    COALESCE(AVG(runs.total_latency_ms), 0.0),
+                    COALESCE(SUM(runs.total_cost), 0.0)
+             FROM runs
+             LEFT JOIN (
+                 SELECT run_id, COUNT(*) AS num_hops FROM hops GROUP BY run_id
+             ) AS hop_counts ON hop_counts.run_id = runs.id
+             WHERE ?1 IS NULL OR runs.timestamp >= ?1",
+            params![since],
+            |row| {
+                Ok(RunStats {
+                    num_runs: row.get(0)?,
+                    avg_hops: row.get(1)?,
+                    avg_latency_ms: row.get(2)?,
+                    total_cost: row.get(3)?,
+                })
+            },
+        )
+        .context("Failed to compute run stats")
+    }
+}
@@ -1,6 +1,12 @@
 use anyhow::{Context, Result};
+use async_stream::try_stream;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 
+use super::client::LlmResponse as ClientLlmResponse;
+use super::sse::SseLineBuffer;
+use super::{StreamChunk, Tool};
+
 #[derive(Debug, Clone)]
 pub struct AnthropicClient {
     client: reqwest::Client,
@@ -13,12 +19,45 @@ struct Message {
     content: String,
 }
 
+/// An Anthropic-style tool definition: same shape as [`Tool`], but the JSON schema lives under
+/// `input_schema` rather than `parameters`.
+#[derive(Debug, Clone, Serialize)]
+struct ApiTool {
+    name: String,
+    description: String,
+    input_schema: serde_json::Value,
+}
+
+impl From<&Tool> for ApiTool {
+    fn from(tool: &Tool) -> Self {
+        Self {
+            name: tool.name.clone(),
+            description: tool.description.clone(),
+            input_schema: tool.parameters.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct ApiRequest {
     model: String,
     max_tokens: u32,
     system: Option<String>,
     messages: Vec<Message>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ApiTool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<ApiToolChoice>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ApiToolChoice {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -32,6 +71,9 @@ pub struct ContentBlock {
     #[serde(rename = "type")]
     pub content_type: String,
     pub text: Option<String>,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub input: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -40,11 +82,19 @@ pub struct Usage {
     pub output_tokens: u32,
 }
 
+/// A `tool_use` content block the model emitted in place of (or alongside) text.
+#[derive(Debug, Clone)]
+pub struct ToolUse {
+    pub name: String,
+    pub input: serde_json::Value,
+}
+
 #[derive(Debug, Clone)]
 pub struct LlmResponse {
     pub text: String,
     pub input_tokens: u32,
     pub output_tokens: u32,
+    pub tool_uses: Vec<ToolUse>,
 }
 
 impl AnthropicClient {
@@ -60,6 +110,21 @@ impl AnthropicClient {
         model: &str,
         system_prompt: Option<&str>,
         user_message: &str,
+    ) -> Result<LlmResponse> {
+        self.complete_with_tools(model, system_prompt, user_message, &[], None)
+            .await
+    }
+
+    /// Like [`complete`](Self::complete), but lets the model call one of `tools` instead of
+    /// (or alongside) returning free text. `tool_choice` is passed through verbatim as a tool
+    /// name, or `None` to let the model decide whether to use a tool at all.
+    pub async fn complete_with_tools(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+        tools: &[Tool],
+        tool_choice: Option<&str>,
     ) -> Result<LlmResponse> {
         let request = ApiRequest {
             model: model.to_string(),
@@ -69,6 +134,16 @@ impl AnthropicClient {
                 role: "user".to_string(),
                 content: user_message.to_string(),
             }],
+            tools: if tools.is_empty() {
+                None
+            } else {
+                Some(tools.iter().map(ApiTool::from).collect())
+            },
+            tool_choice: tool_choice.map(|name| ApiToolChoice {
+                kind: "tool".to_string(),
+                name: Some(name.to_string()),
+            }),
+            stream: None,
         };
 
         let response = self
@@ -100,10 +175,142 @@ impl AnthropicClient {
             .collect::<Vec<_>>()
             .join("");
 
+        let tool_uses = api_response
+            .content
+            .iter()
+            .filter(|block| block.content_type == "tool_use")
+            .filter_map(|block| {
+                Some(ToolUse {
+                    name: block.name.clone()?,
+                    input: block.input.clone().unwrap_or(serde_json::Value::Null),
+                })
+            })
+            .collect();
+
         Ok(LlmResponse {
             text,
             input_tokens: api_response.usage.input_tokens,
             output_tokens: api_response.usage.output_tokens,
+            tool_uses,
         })
     }
+
+    /// Like [`complete`](Self::complete), but streams the response as incremental token
+    /// deltas via Anthropic's `text/event-stream` format, ending with a [`StreamChunk::Done`]
+    /// carrying the same token accounting `complete` would have returned. Tool use isn't
+    /// supported while streaming, mirroring the native API.
+    pub fn complete_stream<'a>(
+        &'a self,
+        model: &'a str,
+        system_prompt: Option<&'a str>,
+        user_message: &'a str,
+    ) -> impl Stream<Item = Result<StreamChunk>> + 'a {
+        try_stream! {
+            let request = ApiRequest {
+                model: model.to_string(),
+                max_tokens: 4096,
+                system: system_prompt.map(|s| s.to_string()),
+                messages: vec![Message {
+                    role: "user".to_string(),
+                    content: user_message.to_string(),
+                }],
+                tools: None,
+                tool_choice: None,
+                stream: Some(true),
+            };
+
+            let response = self
+                .client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", &self.api_key)
+                .header("anthropic-version", "2023-06-01")
+                .header("content-type", "application/json")
+                .json(&request)
+                .send()
+                .await
+                .context("Failed to send request to Anthropic API")?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("Anthropic API error ({}): {}", status, body);
+            }
+
+            let mut text = String::new();
+            let mut input_tokens = 0u32;
+            let mut output_tokens = 0u32;
+            let mut sse = SseLineBuffer::new();
+            let mut bytes = response.bytes_stream();
+
+            while let Some(chunk) = bytes.next().await {
+                let chunk = chunk.context("Failed to read stream chunk")?;
+
+                for line in sse.push(&chunk)? {
+                    let Some(data) = line.strip_prefix("data: ") else {
+                        continue;
+                    };
+
+                    let event: StreamEvent = serde_json::from_str(data)
+                        .context("Failed to parse streamed Anthropic event")?;
+
+                    match event {
+                        StreamEvent::MessageStart { message } => {
+                            input_tokens = message.usage.input_tokens;
+                        }
+                        StreamEvent::ContentBlockDelta { delta } => {
+                            if let Some(delta_text) = delta.text {
+                                if !delta_text.is_empty() {
+                                    text.push_str(&delta_text);
+                                    yield StreamChunk::Delta(delta_text);
+                                }
+                            }
+                        }
+                        StreamEvent::MessageDelta { usage } => {
+                            output_tokens = usage.output_tokens;
+                        }
+                        StreamEvent::Other => {}
+                    }
+                }
+            }
+
+            yield StreamChunk::Done(ClientLlmResponse {
+                text,
+                input_tokens,
+                output_tokens,
+                // The native Anthropic API doesn't report a dollar cost the way OpenRouter does.
+                cost: 0.0,
+                tool_calls: Vec::new(),
+                retries: 0,
+                retry_wait_ms: 0,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type")]
+enum StreamEvent {
+    #[serde(rename = "message_start")]
+    MessageStart { message: MessageStart },
+    #[serde(rename = "content_block_delta")]
+    ContentBlockDelta { delta: ContentBlockDelta },
+    #[serde(rename = "message_delta")]
+    MessageDelta { usage: MessageDeltaUsage },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MessageStart {
+    usage: Usage,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ContentBlockDelta {
+    text: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MessageDeltaUsage {
+    output_tokens: u32,
 }
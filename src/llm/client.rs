@@ -1,25 +1,71 @@
 use anyhow::{Context, Result};
+use async_stream::try_stream;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
 
+use super::sse::SseLineBuffer;
+use crate::retry::{classify_status, RetryOutcome, RetryStrategy};
+
 #[derive(Debug, Clone)]
 pub struct LlmClient {
     client: reqwest::Client,
     api_key: String,
     base_url: String,
+    max_retries: u32,
 }
 
-// OpenAI-compatible chat completions format (used by OpenRouter)
+// OpenAI-compatible chat completions format. `base_url` is configurable, so this same client
+// talks to OpenRouter, OpenAI itself, or any self-hosted server exposing the same schema
+// (TGI, vLLM, Ollama's OpenAI-compatible endpoint, etc).
 #[derive(Debug, Clone, Serialize)]
 struct ChatMessage {
     role: String,
     content: String,
 }
 
+/// A function the model may call, described as an OpenAI-style tool schema.
+#[derive(Debug, Clone, Serialize)]
+pub struct Tool {
+    pub name: String,
+    pub description: String,
+    pub parameters: serde_json::Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ToolSpec {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: Tool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct ChatCompletionRequest {
     model: String,
     max_tokens: u32,
     messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tools: Option<Vec<ToolSpec>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChatCompletionStreamChunk {
+    #[serde(default)]
+    choices: Vec<ChatStreamChoice>,
+    usage: Option<ChatUsage>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChatStreamChoice {
+    delta: ChatStreamDelta,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+struct ChatStreamDelta {
+    content: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -36,6 +82,19 @@ struct ChatChoice {
 #[derive(Debug, Clone, Deserialize)]
 struct ChatChoiceMessage {
     content: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ChatToolCall>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChatToolCall {
+    function: ChatToolCallFunction,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct ChatToolCallFunction {
+    name: String,
+    arguments: String,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -45,20 +104,38 @@ struct ChatUsage {
     cost: Option<f64>,
 }
 
+/// A structured function call the model asked us to perform, in place of free text.
+#[derive(Debug, Clone)]
+pub struct ToolCall {
+    pub name: String,
+    pub arguments: serde_json::Value,
+}
+
+/// One item of a streamed completion: either a token delta, or the terminal usage summary.
+#[derive(Debug, Clone)]
+pub enum StreamChunk {
+    Delta(String),
+    Done(LlmResponse),
+}
+
 #[derive(Debug, Clone)]
 pub struct LlmResponse {
     pub text: String,
     pub input_tokens: u32,
     pub output_tokens: u32,
     pub cost: f64,
+    pub tool_calls: Vec<ToolCall>,
+    pub retries: u32,
+    pub retry_wait_ms: u64,
 }
 
 impl LlmClient {
-    pub fn new(api_key: &str, base_url: &str) -> Self {
+    pub fn new(api_key: &str, base_url: &str, max_retries: u32) -> Self {
         Self {
             client: reqwest::Client::new(),
             api_key: api_key.to_string(),
             base_url: base_url.to_string(),
+            max_retries,
         }
     }
 
@@ -67,6 +144,21 @@ impl LlmClient {
         model: &str,
         system_prompt: Option<&str>,
         user_message: &str,
+    ) -> Result<LlmResponse> {
+        self.complete_with_tools(model, system_prompt, user_message, &[], None)
+            .await
+    }
+
+    /// Like [`complete`](Self::complete), but lets the model call one of `tools` instead of
+    /// (or alongside) returning free text. `tool_choice` is passed through verbatim, e.g.
+    /// `"auto"`, `"required"`, or the name of a specific tool.
+    pub async fn complete_with_tools(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+        tools: &[Tool],
+        tool_choice: Option<&str>,
     ) -> Result<LlmResponse> {
         let mut messages = Vec::new();
         if let Some(system) = system_prompt {
@@ -84,28 +176,58 @@ impl LlmClient {
             model: model.to_string(),
             max_tokens: 4096,
             messages,
+            tools: if tools.is_empty() {
+                None
+            } else {
+                Some(
+                    tools
+                        .iter()
+                        .cloned()
+                        .map(|function| ToolSpec {
+                            kind: "function",
+                            function,
+                        })
+                        .collect(),
+                )
+            },
+            tool_choice: tool_choice.map(|s| s.to_string()),
+            stream: None,
         };
 
-        let response = self
-            .client
-            .post(&self.base_url)
-            .header("Authorization", format!("Bearer {}", &self.api_key))
-            .header("content-type", "application/json")
-            .json(&request)
-            .send()
-            .await
-            .context("Failed to send request to LLM API")?;
+        let mut outcome = RetryOutcome::default();
+        let mut attempt = 0u32;
+        let api_response: ChatCompletionResponse = loop {
+            let response = self
+                .client
+                .post(&self.base_url)
+                .header("Authorization", format!("Bearer {}", &self.api_key))
+                .header("content-type", "application/json")
+                .json(&request)
+                .send()
+                .await
+                .context("Failed to send request to LLM API")?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
-            anyhow::bail!("LLM API error ({}): {}", status, body);
-        }
+            let status = response.status();
+            if status.is_success() {
+                break response
+                    .json()
+                    .await
+                    .context("Failed to parse LLM API response")?;
+            }
 
-        let api_response: ChatCompletionResponse = response
-            .json()
-            .await
-            .context("Failed to parse LLM API response")?;
+            let strategy = classify_status(status);
+            let retry_after = status_retry_after(&response);
+            if strategy == RetryStrategy::GiveUp || attempt >= self.max_retries {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("LLM API error ({}): {}", status, body);
+            }
+
+            let wait = retry_after.unwrap_or_else(|| strategy.backoff(attempt));
+            outcome.retries += 1;
+            outcome.wait_ms += wait.as_millis() as u64;
+            tokio::time::sleep(wait).await;
+            attempt += 1;
+        };
 
         let text = api_response
             .choices
@@ -114,11 +236,136 @@ impl LlmClient {
             .unwrap_or("")
             .to_string();
 
+        let tool_calls = api_response
+            .choices
+            .first()
+            .map(|c| {
+                c.message
+                    .tool_calls
+                    .iter()
+                    .filter_map(|call| {
+                        let arguments =
+                            serde_json::from_str(&call.function.arguments).unwrap_or_default();
+                        Some(ToolCall {
+                            name: call.function.name.clone(),
+                            arguments,
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Ok(LlmResponse {
             text,
             input_tokens: api_response.usage.prompt_tokens,
             output_tokens: api_response.usage.completion_tokens,
             cost: api_response.usage.cost.unwrap_or(0.0),
+            tool_calls,
+            retries: outcome.retries,
+            retry_wait_ms: outcome.wait_ms,
         })
     }
+
+    /// Like [`complete`](Self::complete), but streams the response as incremental token
+    /// deltas via server-sent events, ending with a [`StreamChunk::Done`] carrying the same
+    /// token/cost accounting `complete` would have returned. No retries are attempted: a
+    /// non-success status on the initial request is returned as an error, and partial output
+    /// can't be replayed once the stream has started.
+    pub fn complete_stream<'a>(
+        &'a self,
+        model: &'a str,
+        system_prompt: Option<&'a str>,
+        user_message: &'a str,
+    ) -> impl Stream<Item = Result<StreamChunk>> + 'a {
+        try_stream! {
+            let mut messages = Vec::new();
+            if let Some(system) = system_prompt {
+                messages.push(ChatMessage {
+                    role: "system".to_string(),
+                    content: system.to_string(),
+                });
+            }
+            messages.push(ChatMessage {
+                role: "user".to_string(),
+                content: user_message.to_string(),
+            });
+
+            let request = ChatCompletionRequest {
+                model: model.to_string(),
+                max_tokens: 4096,
+                messages,
+                tools: None,
+                tool_choice: None,
+                stream: Some(true),
+            };
+
+            let response = self
+                .client
+                .post(&self.base_url)
+                .header("Authorization", format!("Bearer {}", &self.api_key))
+                .header("content-type", "application/json")
+                .json(&request)
+                .send()
+                .await
+                .context("Failed to send request to LLM API")?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("LLM API error ({}): {}", status, body);
+            }
+
+            let mut text = String::new();
+            let mut usage: Option<ChatUsage> = None;
+            let mut sse = SseLineBuffer::new();
+            let mut bytes = response.bytes_stream();
+
+            while let Some(chunk) = bytes.next().await {
+                let chunk = chunk.context("Failed to read stream chunk")?;
+
+                for line in sse.push(&chunk)? {
+                    let Some(data) = line.strip_prefix("data: ") else {
+                        continue;
+                    };
+                    if data == "[DONE]" {
+                        continue;
+                    }
+
+                    let parsed: ChatCompletionStreamChunk = serde_json::from_str(data)
+                        .context("Failed to parse streamed LLM chunk")?;
+
+                    if let Some(delta) = parsed.choices.first().and_then(|c| c.delta.content.clone()) {
+                        if !delta.is_empty() {
+                            text.push_str(&delta);
+                            yield StreamChunk::Delta(delta);
+                        }
+                    }
+                    if let Some(parsed_usage) = parsed.usage {
+                        usage = Some(parsed_usage);
+                    }
+                }
+            }
+
+            yield StreamChunk::Done(LlmResponse {
+                text,
+                input_tokens: usage.as_ref().map(|u| u.prompt_tokens).unwrap_or(0),
+                output_tokens: usage.as_ref().map(|u| u.completion_tokens).unwrap_or(0),
+                cost: usage.as_ref().and_then(|u| u.cost).unwrap_or(0.0),
+                tool_calls: Vec::new(),
+                retries: 0,
+                retry_wait_ms: 0,
+            });
+        }
+    }
+}
+
+/// Honor a numeric `Retry-After` header (in seconds) if the server sent one, rather than our
+/// own backoff schedule.
+fn status_retry_after(response: &reqwest::Response) -> Option<std::time::Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.parse::<u64>().ok())
+        .map(std::time::Duration::from_secs)
 }
@@ -0,0 +1,8 @@
+pub mod anthropic;
+pub mod client;
+pub mod provider;
+mod sse;
+
+pub use anthropic::AnthropicClient;
+pub use client::{LlmClient, LlmResponse, StreamChunk, Tool, ToolCall};
+pub use provider::LlmProvider;
@@ -0,0 +1,142 @@
+use std::pin::Pin;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::Stream;
+
+use super::anthropic::AnthropicClient;
+use super::client::LlmClient;
+use super::{LlmResponse, StreamChunk, Tool, ToolCall};
+
+/// Abstracts over wire formats so `Planner`/`Reader`/`Synthesizer` aren't bound to one
+/// provider's request/response shape. `Config` picks an implementation per agent role
+/// (`PLANNER_PROVIDER`, `READER_PROVIDER`, `SYNTHESIZER_PROVIDER`), so a cheap OpenRouter
+/// planner can run alongside a native-Anthropic reader without duplicating agent logic.
+#[async_trait]
+pub trait LlmProvider: Send + Sync {
+    async fn complete(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+    ) -> Result<LlmResponse>;
+
+    /// Default: providers that don't (yet) support tool calling just answer in free text.
+    async fn complete_with_tools(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+        tools: &[Tool],
+        tool_choice: Option<&str>,
+    ) -> Result<LlmResponse> {
+        let _ = (tools, tool_choice);
+        self.complete(model, system_prompt, user_message).await
+    }
+
+    /// Default: providers that don't (yet) support streaming emit the full answer as one
+    /// delta, immediately followed by the terminal usage summary.
+    fn complete_stream<'a>(
+        &'a self,
+        model: &'a str,
+        system_prompt: Option<&'a str>,
+        user_message: &'a str,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send + 'a>> {
+        Box::pin(async_stream::try_stream! {
+            let response = self.complete(model, system_prompt, user_message).await?;
+            yield StreamChunk::Delta(response.text.clone());
+            yield StreamChunk::Done(response);
+        })
+    }
+}
+
+#[async_trait]
+impl LlmProvider for LlmClient {
+    async fn complete(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+    ) -> Result<LlmResponse> {
+        LlmClient::complete(self, model, system_prompt, user_message).await
+    }
+
+    async fn complete_with_tools(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+        tools: &[Tool],
+        tool_choice: Option<&str>,
+    ) -> Result<LlmResponse> {
+        LlmClient::complete_with_tools(self, model, system_prompt, user_message, tools, tool_choice)
+            .await
+    }
+
+    fn complete_stream<'a>(
+        &'a self,
+        model: &'a str,
+        system_prompt: Option<&'a str>,
+        user_message: &'a str,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send + 'a>> {
+        Box::pin(LlmClient::complete_stream(self, model, system_prompt, user_message))
+    }
+}
+
+#[async_trait]
+impl LlmProvider for AnthropicClient {
+    async fn complete(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+    ) -> Result<LlmResponse> {
+        LlmProvider::complete_with_tools(self, model, system_prompt, user_message, &[], None)
+            .await
+    }
+
+    async fn complete_with_tools(
+        &self,
+        model: &str,
+        system_prompt: Option<&str>,
+        user_message: &str,
+        tools: &[Tool],
+        tool_choice: Option<&str>,
+    ) -> Result<LlmResponse> {
+        let response = AnthropicClient::complete_with_tools(
+            self,
+            model,
+            system_prompt,
+            user_message,
+            tools,
+            tool_choice,
+        )
+        .await?;
+        Ok(LlmResponse {
+            text: response.text,
+            input_tokens: response.input_tokens,
+            output_tokens: response.output_tokens,
+            // The native Anthropic API doesn't report a dollar cost the way OpenRouter does.
+            cost: 0.0,
+            tool_calls: response
+                .tool_uses
+                .into_iter()
+                .map(|tool_use| ToolCall {
+                    name: tool_use.name,
+                    arguments: tool_use.input,
+                })
+                .collect(),
+            retries: 0,
+            retry_wait_ms: 0,
+        })
+    }
+
+    fn complete_stream<'a>(
+        &'a self,
+        model: &'a str,
+        system_prompt: Option<&'a str>,
+        user_message: &'a str,
+    ) -> Pin<Box<dyn Stream<Item = Result<StreamChunk>> + Send + 'a>> {
+        Box::pin(AnthropicClient::complete_stream(self, model, system_prompt, user_message))
+    }
+}
@@ -0,0 +1,37 @@
+use anyhow::{Context, Result};
+
+/// Accumulates raw response bytes and hands back complete, trimmed `\n`-terminated lines.
+///
+/// `reqwest`'s `bytes_stream()` splits on arbitrary TCP/HTTP buffer boundaries, not UTF-8
+/// character boundaries or SSE line boundaries, so a multi-byte character (or an entire `data:
+/// ...` line) can straddle two chunks. Buffering raw bytes here and only decoding once a
+/// complete line has arrived avoids both a `U+FFFD`-corrupted stream and a truncated-JSON parse
+/// error — shared by [`LlmClient`](super::LlmClient) and
+/// [`AnthropicClient`](super::AnthropicClient), whose SSE formats otherwise only differ in how
+/// they parse the line past `data: `.
+#[derive(Debug, Default)]
+pub struct SseLineBuffer {
+    buffer: Vec<u8>,
+}
+
+impl SseLineBuffer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a freshly-received chunk and drain every complete line now available, in order.
+    /// Bytes after the last `\n` stay buffered until a future call completes them.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<String>> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut lines = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line = String::from_utf8(self.buffer[..newline].to_vec())
+                .context("Streamed chunk was not valid UTF-8")?;
+            self.buffer.drain(..=newline);
+            lines.push(line.trim().to_string());
+        }
+
+        Ok(lines)
+    }
+}
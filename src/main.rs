@@ -3,14 +3,16 @@ mod config;
 mod instrumentation;
 mod llm;
 mod retrieval;
+mod retry;
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use std::io::BufRead;
+use futures::stream::{self, StreamExt};
+use std::io::{BufRead, Write};
 
 use agent::Agent;
 use config::Config;
-use instrumentation::RunLog;
+use instrumentation::{RunLog, SqliteRunLogger, DEFAULT_DB_PATH};
 
 #[derive(Parser)]
 #[command(name = "agentic-search", about = "Multi-hop research agent over a large corpus")]
@@ -21,6 +23,11 @@ struct Cli {
     /// Enable verbose per-hop output
     #[arg(short, long, global = true)]
     verbose: bool,
+
+    /// Serve Prometheus-format metrics on this port for the duration of the command, so a long
+    /// `eval` run can be watched on a dashboard instead of only via the final summary
+    #[arg(long, global = true)]
+    serve_metrics: Option<u16>,
 }
 
 #[derive(Subcommand)]
@@ -34,6 +41,35 @@ enum Commands {
     Eval {
         /// Path to JSONL file with questions
         path: String,
+
+        /// Number of questions to research concurrently
+        #[arg(long, default_value_t = 4)]
+        concurrency: usize,
+    },
+    /// Inspect past runs recorded with RUN_LOG_BACKEND=sqlite
+    History {
+        #[command(subcommand)]
+        action: HistoryAction,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryAction {
+    /// List the most recent runs
+    List {
+        #[arg(long, default_value_t = 20)]
+        limit: u32,
+    },
+    /// Show a single run by id, hops included
+    Show {
+        /// The run's UUID
+        id: String,
+    },
+    /// Aggregate stats (avg hops, avg latency, total cost) across runs
+    Stats {
+        /// Only include runs at or after this RFC 3339 timestamp
+        #[arg(long)]
+        since: Option<String>,
     },
 }
 
@@ -56,43 +92,80 @@ async fn main() -> Result<()> {
         .init();
 
     let cli = Cli::parse();
+
+    if let Some(port) = cli.serve_metrics {
+        tokio::spawn(async move {
+            if let Err(err) = instrumentation::metrics_server::serve(port).await {
+                tracing::error!("Metrics server exited: {}", err);
+            }
+        });
+    }
+
+    // `history` only queries the run log database — it doesn't need an LLM/Qdrant-backed Agent.
+    let command = match cli.command {
+        Commands::History { action } => return run_history(action),
+        command => command,
+    };
+
     let config = Config::from_env()?;
     let agent = Agent::new(config).await?;
 
-    match cli.command {
+    match command {
         Commands::Ask { question } => {
-            let run_log = agent.ask(&question, cli.verbose).await?;
-            println!("\n{}\n", run_log.final_answer);
-            println!("{}", run_log.summary());
+            println!();
+            let run_log = agent
+                .ask_streaming(&question, cli.verbose, |chunk| {
+                    print!("{}", chunk);
+                    let _ = std::io::stdout().flush();
+                })
+                .await?;
+            println!("\n\n{}", run_log.summary());
         }
-        Commands::Eval { path } => {
+        Commands::Eval { path, concurrency } => {
             let file =
                 std::fs::File::open(&path).context(format!("Failed to open eval file: {}", path))?;
             let reader = std::io::BufReader::new(file);
 
-            let mut run_logs: Vec<RunLog> = Vec::new();
-            let mut errors = 0;
-
+            let mut questions: Vec<EvalQuestion> = Vec::new();
             for (i, line) in reader.lines().enumerate() {
                 let line = line.context("Failed to read line")?;
                 if line.trim().is_empty() {
                     continue;
                 }
-
-                let eq: EvalQuestion =
-                    serde_json::from_str(&line).context(format!("Failed to parse line {}", i + 1))?;
-
-                eprintln!("\n[{}/...] {}", i + 1, eq.question);
-
-                match agent.ask(&eq.question, cli.verbose).await {
-                    Ok(run_log) => {
-                        println!("  {}", run_log.summary());
-                        run_logs.push(run_log);
-                    }
-                    Err(e) => {
-                        eprintln!("  ERROR: {}", e);
-                        errors += 1;
+                questions.push(
+                    serde_json::from_str(&line).context(format!("Failed to parse line {}", i + 1))?,
+                );
+            }
+            let total = questions.len();
+
+            // Research questions up to `concurrency` at a time; results are collected
+            // out of completion order, then sorted back to question order below so the
+            // error count and summary stay deterministic regardless of scheduling. Each
+            // question's own summary or error line is printed as soon as it finishes.
+            let mut results: Vec<(usize, Result<RunLog>)> = stream::iter(questions.into_iter().enumerate())
+                .map(|(i, eq)| {
+                    let agent = &agent;
+                    async move {
+                        eprintln!("\n[{}/{}] {}", i + 1, total, eq.question);
+                        let result = agent.ask(&eq.question, cli.verbose).await;
+                        match &result {
+                            Ok(run_log) => println!("  {}", run_log.summary()),
+                            Err(e) => eprintln!("  ERROR: {}", e),
+                        }
+                        (i, result)
                     }
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+            results.sort_by_key(|(i, _)| *i);
+
+            let mut run_logs: Vec<RunLog> = Vec::new();
+            let mut errors = 0;
+            for (_, result) in results {
+                match result {
+                    Ok(run_log) => run_logs.push(run_log),
+                    Err(_) => errors += 1,
                 }
             }
 
@@ -105,7 +178,7 @@ async fn main() -> Result<()> {
                 let avg_latency = run_logs.iter().map(|r| r.total_latency_ms).sum::<u64>() as f64
                     / run_logs.len() as f64;
                 let total_tokens: u32 = run_logs.iter().map(|r| r.total_tokens()).sum();
-                let total_cost: f64 = run_logs.iter().map(|r| r.estimated_cost()).sum();
+                let total_cost: f64 = run_logs.iter().map(|r| r.cost()).sum();
 
                 println!("Avg hops: {:.1}", avg_hops);
                 println!("Avg latency: {:.1}s", avg_latency / 1000.0);
@@ -113,6 +186,43 @@ async fn main() -> Result<()> {
                 println!("Total cost: ${:.4}", total_cost);
             }
         }
+        Commands::History { .. } => unreachable!("handled above"),
+    }
+
+    Ok(())
+}
+
+fn run_history(action: HistoryAction) -> Result<()> {
+    let store = SqliteRunLogger::new(DEFAULT_DB_PATH)
+        .context(format!("Failed to open run log database at {}", DEFAULT_DB_PATH))?;
+
+    match action {
+        HistoryAction::List { limit } => {
+            for run in store.list_recent(limit)? {
+                println!(
+                    "{}  {}  hops={:<3} latency={:>7.1}s cost=${:<7.4} {}",
+                    run.id,
+                    run.timestamp,
+                    run.num_hops,
+                    run.total_latency_ms as f64 / 1000.0,
+                    run.total_cost,
+                    run.question,
+                );
+            }
+        }
+        HistoryAction::Show { id } => {
+            let run_log = store
+                .get(&id)?
+                .with_context(|| format!("No run found with id {}", id))?;
+            println!("{}", serde_json::to_string_pretty(&run_log)?);
+        }
+        HistoryAction::Stats { since } => {
+            let stats = store.stats(since.as_deref())?;
+            println!("Runs: {}", stats.num_runs);
+            println!("Avg hops: {:.1}", stats.avg_hops);
+            println!("Avg latency: {:.1}s", stats.avg_latency_ms / 1000.0);
+            println!("Total cost: ${:.4}", stats.total_cost);
+        }
     }
 
     Ok(())
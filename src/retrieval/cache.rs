@@ -0,0 +1,104 @@
+use anyhow::{Context, Result};
+use qdrant_client::qdrant::{
+    Document, PointStruct, Query, QueryPointsBuilder, ScoredPoint, UpsertPointsBuilder,
+};
+use qdrant_client::Qdrant;
+
+use super::qdrant::Passage;
+
+/// Caches query -> passages lookups in a dedicated Qdrant collection so near-duplicate
+/// follow-up queries across hops can skip the search round-trip. The query is still embedded
+/// for the lookup itself.
+pub struct SemanticCache {
+    client: Qdrant,
+    collection: String,
+    embedding_model: String,
+    similarity_threshold: f32,
+}
+
+impl SemanticCache {
+    pub async fn new(
+        url: &str,
+        api_key: Option<&str>,
+        collection: &str,
+        embedding_model: &str,
+        similarity_threshold: f32,
+    ) -> Result<Self> {
+        let mut builder = Qdrant::from_url(url);
+        if let Some(key) = api_key {
+            builder = builder.api_key(key);
+        }
+        let client = builder
+            .build()
+            .context("Failed to connect to Qdrant cache collection")?;
+
+        Ok(Self {
+            client,
+            collection: collection.to_string(),
+            embedding_model: embedding_model.to_string(),
+            similarity_threshold,
+        })
+    }
+
+    /// Look up `query_text` in the cache. Returns the stored passages on a hit (cosine
+    /// similarity at or above `similarity_threshold`), or `None` on a miss.
+    ///
+    /// Queries by `vector` when the caller already computed one client-side (`cloud_inference =
+    /// false`), so the cache never requires Qdrant-side inference in an offline deployment.
+    /// Falls back to `Document::new` server-side inference only when `vector` is `None`.
+    pub async fn get(&self, query_text: &str, vector: Option<&[f32]>) -> Result<Option<Vec<Passage>>> {
+        let query = match vector {
+            Some(vector) => Query::new_nearest(vector.to_vec()),
+            None => Query::new_nearest(Document::new(query_text, &self.embedding_model)),
+        };
+
+        let results = self
+            .client
+            .query(
+                QueryPointsBuilder::new(&self.collection)
+                    .query(query)
+                    .limit(1)
+                    .with_payload(true),
+            )
+            .await
+            .context("Semantic cache lookup failed")?;
+
+        match results.result.first() {
+            Some(point) if point.score >= self.similarity_threshold => {
+                Ok(Self::extract_passages(point))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Store `passages` under `query_text` for future near-duplicate lookups. Upserts by
+    /// `vector` when one is given, mirroring [`get`](Self::get)'s fallback to server-side
+    /// inference only when `vector` is `None`.
+    pub async fn put(&self, query_text: &str, vector: Option<&[f32]>, passages: &[Passage]) -> Result<()> {
+        let passages_json =
+            serde_json::to_string(passages).context("Failed to serialize cached passages")?;
+        let payload = [("passages_json".to_string(), passages_json.into())].into();
+        let point = match vector {
+            Some(vector) => {
+                PointStruct::new(uuid::Uuid::new_v4().to_string(), vector.to_vec(), payload)
+            }
+            None => PointStruct::new(
+                uuid::Uuid::new_v4().to_string(),
+                Document::new(query_text, &self.embedding_model),
+                payload,
+            ),
+        };
+
+        self.client
+            .upsert_points(UpsertPointsBuilder::new(&self.collection, vec![point]))
+            .await
+            .context("Semantic cache upsert failed")?;
+
+        Ok(())
+    }
+
+    fn extract_passages(point: &ScoredPoint) -> Option<Vec<Passage>> {
+        let passages_json = point.payload.get("passages_json")?.as_str()?;
+        serde_json::from_str(passages_json).ok()
+    }
+}
@@ -0,0 +1,44 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use fastembed::{EmbeddingModel, TextEmbedding, InitOptions};
+
+use super::Embedder;
+
+pub struct FastEmbedLocal {
+    model: TextEmbedding,
+    dimensions: usize,
+}
+
+impl FastEmbedLocal {
+    pub fn new(model_name: &str) -> Result<Self> {
+        let (model_type, dimensions) = match model_name {
+            "sentence-transformers/all-MiniLM-L6-v2" | "all-MiniLM-L6-v2" => {
+                (EmbeddingModel::AllMiniLML6V2, 384)
+            }
+            "mixedbread-ai/mxbai-embed-large-v1" => (EmbeddingModel::MxbaiEmbedLargeV1, 1024),
+            "nomic-ai/nomic-embed-text-v1.5" => (EmbeddingModel::NomicEmbedTextV15, 768),
+            _ => anyhow::bail!("Unsupported embedding model: {}", model_name),
+        };
+
+        let model = TextEmbedding::try_new(InitOptions::new(model_type).with_show_download_progress(true))
+            .context("Failed to initialize embedding model")?;
+
+        Ok(Self { model, dimensions })
+    }
+}
+
+#[async_trait]
+impl Embedder for FastEmbedLocal {
+    async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let texts: Vec<String> = texts.iter().map(|t| t.to_string()).collect();
+        let embeddings = self
+            .model
+            .embed(texts, None)
+            .context("Failed to generate embeddings")?;
+        Ok(embeddings)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
@@ -0,0 +1,18 @@
+mod local;
+mod ollama;
+mod openai;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub use local::FastEmbedLocal;
+pub use ollama::OllamaEmbedder;
+pub use openai::OpenAiEmbedder;
+
+/// A source of text embeddings, abstracting over local inference and hosted embedding APIs so
+/// `QdrantRetriever` doesn't need to know which one produced the vector it's querying with.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>>;
+    fn dimensions(&self) -> usize;
+}
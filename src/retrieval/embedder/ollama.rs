@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::Embedder;
+
+/// Client-side embeddings via a local Ollama server's `/api/embeddings` endpoint.
+pub struct OllamaEmbedder {
+    client: reqwest::Client,
+    base_url: String,
+    model: String,
+    dimensions: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct OllamaEmbedRequest<'a> {
+    model: &'a str,
+    prompt: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    embedding: Vec<f32>,
+}
+
+impl OllamaEmbedder {
+    pub fn new(base_url: &str, model: &str, dimensions: usize) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.to_string(),
+            model: model.to_string(),
+            dimensions,
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for OllamaEmbedder {
+    async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        // Ollama's /api/embeddings endpoint embeds one prompt per request.
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            let response = self
+                .client
+                .post(format!("{}/api/embeddings", self.base_url))
+                .json(&OllamaEmbedRequest {
+                    model: &self.model,
+                    prompt: text,
+                })
+                .send()
+                .await
+                .context("Failed to send request to Ollama")?;
+
+            let status = response.status();
+            if !status.is_success() {
+                let body = response.text().await.unwrap_or_default();
+                anyhow::bail!("Ollama embeddings error ({}): {}", status, body);
+            }
+
+            let parsed: OllamaEmbedResponse = response
+                .json()
+                .await
+                .context("Failed to parse Ollama embeddings response")?;
+            embeddings.push(parsed.embedding);
+        }
+
+        Ok(embeddings)
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::Embedder;
+
+/// Client-side embeddings via an OpenAI-compatible `/embeddings` endpoint (OpenAI or
+/// OpenRouter), authenticated the same way as [`crate::llm::LlmClient`].
+pub struct OpenAiEmbedder {
+    client: reqwest::Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+    dimensions: usize,
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingRequest<'a> {
+    model: &'a str,
+    input: &'a [&'a str],
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    embedding: Vec<f32>,
+}
+
+impl OpenAiEmbedder {
+    pub fn new(api_key: &str, base_url: &str, model: &str, dimensions: usize) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_key: api_key.to_string(),
+            base_url: base_url.to_string(),
+            model: model.to_string(),
+            dimensions,
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for OpenAiEmbedder {
+    async fn embed(&self, texts: &[&str]) -> Result<Vec<Vec<f32>>> {
+        let request = EmbeddingRequest {
+            model: &self.model,
+            input: texts,
+        };
+
+        let response = self
+            .client
+            .post(&self.base_url)
+            .header("Authorization", format!("Bearer {}", &self.api_key))
+            .header("content-type", "application/json")
+            .json(&request)
+            .send()
+            .await
+            .context("Failed to send request to embeddings API")?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            anyhow::bail!("Embeddings API error ({}): {}", status, body);
+        }
+
+        let api_response: EmbeddingResponse = response
+            .json()
+            .await
+            .context("Failed to parse embeddings API response")?;
+
+        Ok(api_response
+            .data
+            .into_iter()
+            .map(|d| d.embedding)
+            .collect())
+    }
+
+    fn dimensions(&self) -> usize {
+        self.dimensions
+    }
+}
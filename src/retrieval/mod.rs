@@ -1,5 +1,7 @@
+pub mod cache;
 pub mod embedder;
 pub mod qdrant;
 
-pub use embedder::Embedder;
+pub use cache::SemanticCache;
+pub use embedder::{Embedder, FastEmbedLocal, OllamaEmbedder, OpenAiEmbedder};
 pub use qdrant::QdrantRetriever;
@@ -1,8 +1,11 @@
 use anyhow::{Context, Result};
 use qdrant_client::qdrant::{Document, Query, QueryPointsBuilder, ScoredPoint};
 use qdrant_client::Qdrant;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+use crate::retry::{RetryOutcome, RetryStrategy};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Passage {
     pub text: String,
 }
@@ -11,6 +14,7 @@ pub struct QdrantRetriever {
     client: Qdrant,
     collection: String,
     embedding_model: String,
+    max_retries: u32,
 }
 
 impl QdrantRetriever {
@@ -19,6 +23,7 @@ impl QdrantRetriever {
         api_key: Option<&str>,
         collection: &str,
         embedding_model: &str,
+        max_retries: u32,
     ) -> Result<Self> {
         let mut builder = Qdrant::from_url(url);
         if let Some(key) = api_key {
@@ -30,26 +35,58 @@ impl QdrantRetriever {
             client,
             collection: collection.to_string(),
             embedding_model: embedding_model.to_string(),
+            max_retries,
         })
     }
 
     /// Search using Qdrant cloud inference (server-side embedding).
-    pub async fn search(&self, query_text: &str, top_k: u64) -> Result<Vec<Passage>> {
-        let results = self
-            .client
-            .query(
-                QueryPointsBuilder::new(&self.collection)
-                    .query(Query::new_nearest(Document::new(
-                        query_text,
-                        &self.embedding_model,
-                    )))
-                    .limit(top_k)
-                    .with_payload(true),
-            )
-            .await
-            .context("Qdrant query failed")?;
+    pub async fn search(&self, query_text: &str, top_k: u64) -> Result<(Vec<Passage>, RetryOutcome)> {
+        self.query_with_retry(
+            Query::new_nearest(Document::new(query_text, &self.embedding_model)),
+            top_k,
+        )
+        .await
+    }
 
-        Ok(Self::extract_passages(results.result))
+    /// Search by a pre-computed query vector, for use when embeddings are generated
+    /// client-side (`cloud_inference = false`) instead of by Qdrant's server-side inference.
+    pub async fn search_with_vector(
+        &self,
+        vector: Vec<f32>,
+        top_k: u64,
+    ) -> Result<(Vec<Passage>, RetryOutcome)> {
+        self.query_with_retry(Query::new_nearest(vector), top_k).await
+    }
+
+    /// Shared retry loop for both search modes, which otherwise only differ in how `query` was
+    /// built (text for server-side inference vs. a pre-computed vector).
+    async fn query_with_retry(&self, query: Query, top_k: u64) -> Result<(Vec<Passage>, RetryOutcome)> {
+        let mut outcome = RetryOutcome::default();
+        let mut attempt = 0u32;
+
+        loop {
+            match self
+                .client
+                .query(
+                    QueryPointsBuilder::new(&self.collection)
+                        .query(query.clone())
+                        .limit(top_k)
+                        .with_payload(true),
+                )
+                .await
+            {
+                Ok(results) => return Ok((Self::extract_passages(results.result), outcome)),
+                Err(err) => {
+                    let strategy = classify_qdrant_error(&err);
+                    if strategy == RetryStrategy::GiveUp || attempt >= self.max_retries {
+                        return Err(err).context("Qdrant query failed");
+                    }
+                    outcome.record(strategy, attempt);
+                    tokio::time::sleep(strategy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+            }
+        }
     }
 
     fn extract_passages(points: Vec<ScoredPoint>) -> Vec<Passage> {
@@ -68,3 +105,23 @@ impl QdrantRetriever {
             .collect()
     }
 }
+
+/// Classify a Qdrant client error into a [`RetryStrategy`]. `qdrant-client` doesn't expose a
+/// structured status code for every transport it supports, so we fall back to matching the
+/// error text for the cases that matter: rate limiting and permanent client errors.
+fn classify_qdrant_error(err: &qdrant_client::QdrantError) -> RetryStrategy {
+    let msg = err.to_string().to_lowercase();
+    if msg.contains("429") || msg.contains("resource_exhausted") || msg.contains("rate limit") {
+        RetryStrategy::RetryAfterRateLimit
+    } else if msg.contains("400")
+        || msg.contains("404")
+        || msg.contains("invalid_argument")
+        || msg.contains("not_found")
+        || msg.contains("unauthenticated")
+        || msg.contains("permission_denied")
+    {
+        RetryStrategy::GiveUp
+    } else {
+        RetryStrategy::Retry
+    }
+}
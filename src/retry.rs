@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+/// How to react to a failed network call, decided per attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryStrategy {
+    /// Not worth retrying (e.g. a 4xx other than 429) — surface the error to the caller.
+    GiveUp,
+    /// A transient server-side failure (e.g. 5xx) — back off and try again.
+    Retry,
+    /// The server asked us to slow down — back off longer than a plain retry.
+    RetryAfterRateLimit,
+}
+
+impl RetryStrategy {
+    /// How long to wait before the next attempt. `attempt` is the zero-based index of the retry
+    /// about to be made, so it is 0 after the first failed call.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        match self {
+            RetryStrategy::GiveUp => Duration::ZERO,
+            RetryStrategy::Retry => Duration::from_millis(10u64.pow(attempt)),
+            RetryStrategy::RetryAfterRateLimit => Duration::from_millis(100 + 10u64.pow(attempt)),
+        }
+    }
+}
+
+/// Classify an HTTP status code into a [`RetryStrategy`]. Callers should only invoke this on a
+/// non-success status.
+pub fn classify_status(status: reqwest::StatusCode) -> RetryStrategy {
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        RetryStrategy::RetryAfterRateLimit
+    } else if status.is_server_error() {
+        RetryStrategy::Retry
+    } else {
+        RetryStrategy::GiveUp
+    }
+}
+
+/// How many times a retry loop ended up retrying, and how long it spent waiting — recorded in
+/// `HopLog` so `runs.jsonl` shows where time was lost to flakiness.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RetryOutcome {
+    pub retries: u32,
+    pub wait_ms: u64,
+}
+
+impl RetryOutcome {
+    pub fn record(&mut self, strategy: RetryStrategy, attempt: u32) {
+        self.retries += 1;
+        self.wait_ms += strategy.backoff(attempt).as_millis() as u64;
+    }
+}